@@ -0,0 +1,45 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use dom::bindings::codegen::Bindings::CSSStyleValueBinding;
+use dom::bindings::codegen::Bindings::CSSStyleValueBinding::CSSStyleValueMethods;
+use dom::bindings::js::Root;
+use dom::bindings::reflector::{Reflector, reflect_dom_object};
+use dom::bindings::str::DOMString;
+use dom::paintworkletglobalscope::PaintWorkletGlobalScope;
+use dom_struct::dom_struct;
+
+/// https://drafts.css-houdini.org/css-typed-om/#cssstylevalue
+///
+/// Servo does not implement the rest of the Typed OM hierarchy yet, so
+/// every parsed paint argument is represented as a generic `CSSStyleValue`
+/// carrying its serialization, rather than one of the typed subclasses
+/// (`CSSUnitValue`, `CSSKeywordValue`, etc) that the spec calls for.
+#[dom_struct]
+pub struct CSSStyleValue {
+    reflector: Reflector,
+    value: DOMString,
+}
+
+impl CSSStyleValue {
+    fn new_inherited(value: DOMString) -> CSSStyleValue {
+        CSSStyleValue {
+            reflector: Reflector::new(),
+            value: value,
+        }
+    }
+
+    pub fn new(global: &PaintWorkletGlobalScope, value: DOMString) -> Root<CSSStyleValue> {
+        reflect_dom_object(box CSSStyleValue::new_inherited(value),
+                            global,
+                            CSSStyleValueBinding::Wrap)
+    }
+}
+
+impl CSSStyleValueMethods for CSSStyleValue {
+    /// https://drafts.css-houdini.org/css-typed-om/#serialize-a-cssstylevalue
+    fn Stringifier(&self) -> DOMString {
+        self.value.clone()
+    }
+}
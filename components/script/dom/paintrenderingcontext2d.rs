@@ -0,0 +1,140 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use app_units::Au;
+use canvas_traits::CanvasData;
+use canvas_traits::CanvasImageData;
+use dom::bindings::cell::DOMRefCell;
+use dom::bindings::codegen::Bindings::PaintRenderingContext2DBinding;
+use dom::bindings::js::JS;
+use dom::bindings::js::Root;
+use dom::bindings::reflector::{Reflector, reflect_dom_object};
+use dom::paintworkletglobalscope::PaintWorkletGlobalScope;
+use dom_struct::dom_struct;
+use euclid::Size2D;
+use ipc_channel::ipc::IpcSender;
+use ipc_channel::ipc::IpcSharedMemory;
+use net_traits::image::base::Image;
+use net_traits::image::base::PixelFormat;
+use std::cell::Cell;
+
+/// https://drafts.css-houdini.org/css-paint-api/#paintrenderingcontext2d
+///
+/// Servo does not implement the 2D drawing operations (`fillRect` and
+/// friends) for this type yet; it owns the backing bitmap and the
+/// physical/CSS pixel scale that those operations would need to consult
+/// once added.
+#[dom_struct]
+pub struct PaintRenderingContext2D {
+    reflector: Reflector,
+    global: JS<PaintWorkletGlobalScope>,
+    #[ignore_heap_size_of = "Defined in std"]
+    bitmap: DOMRefCell<Vec<u8>>,
+    width: Cell<u32>,
+    height: Cell<u32>,
+    device_pixel_ratio: Cell<f32>,
+    alpha: Cell<bool>,
+}
+
+impl PaintRenderingContext2D {
+    fn new_inherited(global: &PaintWorkletGlobalScope) -> PaintRenderingContext2D {
+        PaintRenderingContext2D {
+            reflector: Reflector::new(),
+            global: JS::from_ref(global),
+            bitmap: DOMRefCell::new(Vec::new()),
+            width: Cell::new(0),
+            height: Cell::new(0),
+            device_pixel_ratio: Cell::new(1.0),
+            alpha: Cell::new(true),
+        }
+    }
+
+    pub fn new(global: &PaintWorkletGlobalScope) -> Root<PaintRenderingContext2D> {
+        reflect_dom_object(box PaintRenderingContext2D::new_inherited(global),
+                            global,
+                            PaintRenderingContext2DBinding::Wrap)
+    }
+
+    /// Allocate (or reallocate) the backing bitmap at `size`, scaled from CSS
+    /// pixels to physical pixels by `device_pixel_ratio`, and clear it ready
+    /// for the paint callback to draw into.
+    ///
+    /// The initial bitmap is "transparent black" per the 2D canvas spec,
+    /// except that a `{ alpha: false }` context is always opaque, so it
+    /// clears to opaque (rather than transparent) black instead.
+    /// https://drafts.css-houdini.org/css-paint-api/#dom-paintrenderingcontext2dsettings-alpha
+    pub fn set_bitmap_dimensions(&self, size: Size2D<Au>, device_pixel_ratio: f32, alpha: bool) {
+        let (width, height) = physical_pixel_size(size, device_pixel_ratio);
+        self.width.set(width);
+        self.height.set(height);
+        self.device_pixel_ratio.set(device_pixel_ratio);
+        self.alpha.set(alpha);
+
+        let clear_pixel = initial_clear_pixel(alpha);
+        let len = (width as usize) * (height as usize) * 4;
+        *self.bitmap.borrow_mut() = clear_pixel.iter().cloned().cycle().take(len).collect();
+
+        // Only the bitmap size is scaled: `device_pixel_ratio` is recorded in
+        // `self.device_pixel_ratio`, but this type has no transform and no 2D
+        // drawing operations (`fillRect`, `drawImage`, etc), so nothing maps
+        // CSS-pixel coordinates onto the physical-pixel bitmap.
+    }
+
+    /// Send the current contents of the bitmap to the compositor.
+    /// https://drafts.css-houdini.org/css-paint-api/#draw-a-paint-image (Step 8)
+    pub fn send_data(&self, sender: IpcSender<CanvasData>) {
+        let mut image = Image {
+            width: self.width.get(),
+            height: self.height.get(),
+            format: PixelFormat::BGRA8,
+            bytes: IpcSharedMemory::from_bytes(&*self.bitmap.borrow()),
+            id: None,
+        };
+        self.global.image_cache().set_webrender_image_key(&mut image);
+        let image_key = image.id.expect("Image cache should set image key.");
+        let image_data = CanvasImageData { image_key: image_key };
+        let _ = sender.send(CanvasData::Image(image_data));
+    }
+}
+
+/// Convert a CSS-pixel size into a physical-pixel size by scaling by the
+/// device pixel ratio.
+fn physical_pixel_size(size: Size2D<Au>, device_pixel_ratio: f32) -> (u32, u32) {
+    let width = (size.width.to_px().abs() as f32 * device_pixel_ratio) as u32;
+    let height = (size.height.to_px().abs() as f32 * device_pixel_ratio) as u32;
+    (width, height)
+}
+
+/// The BGRA8 pixel a freshly-(re)sized bitmap is cleared to: transparent
+/// black, unless the context was created with `{ alpha: false }`, in which
+/// case it is always opaque.
+/// https://drafts.css-houdini.org/css-paint-api/#dom-paintrenderingcontext2dsettings-alpha
+fn initial_clear_pixel(alpha: bool) -> [u8; 4] {
+    if alpha { [0, 0, 0, 0] } else { [0, 0, 0, 0xFF] }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::initial_clear_pixel;
+    use super::physical_pixel_size;
+    use app_units::Au;
+    use euclid::Size2D;
+
+    #[test]
+    fn physical_pixel_size_scales_by_device_pixel_ratio() {
+        let size = Size2D::new(Au::from_px(10), Au::from_px(20));
+        assert_eq!(physical_pixel_size(size, 1.0), (10, 20));
+        assert_eq!(physical_pixel_size(size, 2.0), (20, 40));
+    }
+
+    #[test]
+    fn initial_clear_pixel_is_transparent_when_alpha_is_true() {
+        assert_eq!(initial_clear_pixel(true), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn initial_clear_pixel_is_opaque_when_alpha_is_false() {
+        assert_eq!(initial_clear_pixel(false), [0, 0, 0, 0xFF]);
+    }
+}
@@ -19,8 +19,10 @@ use dom::bindings::js::JS;
 use dom::bindings::js::Root;
 use dom::bindings::reflector::DomObject;
 use dom::bindings::str::DOMString;
+use dom::cssstylevalue::CSSStyleValue;
 use dom::paintrenderingcontext2d::PaintRenderingContext2D;
 use dom::paintsize::PaintSize;
+use dom::stylepropertymapreadonly::StylePropertyMapReadOnly;
 use dom::workletglobalscope::WorkletGlobalScope;
 use dom::workletglobalscope::WorkletGlobalScopeInit;
 use dom_struct::dom_struct;
@@ -37,6 +39,7 @@ use js::jsapi::IsConstructor;
 use js::jsapi::JSAutoCompartment;
 use js::jsapi::JS_ClearPendingException;
 use js::jsapi::JS_IsExceptionPending;
+use js::jsapi::JS_NewArrayObject;
 use js::jsval::JSVal;
 use js::jsval::ObjectValue;
 use js::jsval::UndefinedValue;
@@ -87,18 +90,28 @@ impl PaintWorkletGlobalScope {
 
     pub fn perform_a_worklet_task(&self, task: PaintWorkletTask) {
         match task {
-            PaintWorkletTask::DrawAPaintImage(name, size, sender) => self.draw_a_paint_image(name, size, sender),
+            PaintWorkletTask::DrawAPaintImage(name, size, device_pixel_ratio, properties, arguments, sender) =>
+                self.draw_a_paint_image(name, size, device_pixel_ratio, properties, arguments, sender),
         }
     }
 
+    /// The image cache, used by a `PaintRenderingContext2D` to turn its
+    /// bitmap into a webrender image key once a paint callback has run.
+    pub(crate) fn image_cache(&self) -> Arc<ImageCache> {
+        self.image_cache.clone()
+    }
+
     /// https://drafts.css-houdini.org/css-paint-api/#draw-a-paint-image
     fn draw_a_paint_image(&self,
                           name: Atom,
                           size: Size2D<Au>,
+                          device_pixel_ratio: f32,
+                          properties: Vec<(Atom, String)>,
+                          arguments: Vec<String>,
                           sender: IpcSender<CanvasData>)
     {
         // TODO: document paint definitions.
-        self.invoke_a_paint_callback(name, size, sender);
+        self.invoke_a_paint_callback(name, size, device_pixel_ratio, properties, arguments, sender);
     }
 
     /// https://drafts.css-houdini.org/css-paint-api/#invoke-a-paint-callback
@@ -106,6 +119,9 @@ impl PaintWorkletGlobalScope {
     fn invoke_a_paint_callback(&self,
                                name: Atom,
                                size: Size2D<Au>,
+                               device_pixel_ratio: f32,
+                               properties: Vec<(Atom, String)>,
+                               arguments: Vec<String>,
                                sender: IpcSender<CanvasData>)
     {
         let width = size.width.to_px().abs() as u32;
@@ -119,28 +135,34 @@ impl PaintWorkletGlobalScope {
         // Step 2.2-5.1.
         rooted!(in(cx) let mut class_constructor = UndefinedValue());
         rooted!(in(cx) let mut paint_function = UndefinedValue());
-        let rendering_context = match self.paint_definitions.borrow().get(&name) {
+        let mut argument_syntax = Vec::new();
+        let mut context_settings = PaintRenderingContext2DSettings::default();
+        let mut input_properties = Vec::new();
+        match self.paint_definitions.borrow().get(&name) {
             None => {
                 // Step 2.2.
                 warn!("Drawing un-registered paint definition {}.", name);
-                return self.send_invalid_image(size, sender);
+                return self.send_invalid_image(size, device_pixel_ratio, sender);
             }
             Some(definition) => {
                 // Step 5.1
                 if !definition.constructor_valid_flag.get() {
                     debug!("Drawing invalid paint definition {}.", name);
-                    return self.send_invalid_image(size, sender);
+                    return self.send_invalid_image(size, device_pixel_ratio, sender);
                 }
                 class_constructor.set(definition.class_constructor.get());
                 paint_function.set(definition.paint_function.get());
-                Root::from_ref(&*definition.context)
+                argument_syntax = definition.argument_syntax.clone();
+                context_settings = definition.context_settings.clone();
+                input_properties = definition.input_properties.clone();
             }
         };
 
         // Steps 5.2-5.4
-        // TODO: the spec requires calling the constructor now, but we might want to
-        // prepopulate the paint instance in `RegisterPaint`, to avoid calling it in
-        // the primary worklet thread.
+        // `RegisterPaint` already prepopulates the paint class instance (see Step 21
+        // below), so the common case is an `Entry::Occupied` lookup here. The
+        // `Entry::Vacant` branch remains as a fallback, e.g. for a scope in the
+        // global scope pool that was added after `RegisterPaint` ran.
         // https://github.com/servo/servo/issues/17377
         rooted!(in(cx) let mut paint_instance = UndefinedValue());
         match self.paint_class_instances.borrow_mut().entry(name.clone()) {
@@ -157,7 +179,7 @@ impl PaintWorkletGlobalScope {
                     self.paint_definitions.borrow_mut().get_mut(&name)
                         .expect("Vanishing paint definition.")
                         .constructor_valid_flag.set(false);
-                    return self.send_invalid_image(size, sender);
+                    return self.send_invalid_image(size, device_pixel_ratio, sender);
                 }
                 // Step 5.4
                 entry.insert(Box::new(Heap::default())).set(paint_instance.get());
@@ -165,20 +187,43 @@ impl PaintWorkletGlobalScope {
         };
 
         // TODO: Steps 6-7
-        // Step 8
-        // TODO: the spec requires creating a new paint rendering context each time,
-        // this code recycles the same one.
-        rendering_context.set_bitmap_dimensions(size);
+        // Step 8: a fresh rendering context is created for every invocation, so that
+        // interleaved or concurrent paints can't corrupt each other's bitmaps.
+        // The backing bitmap is allocated at the physical (device pixel) size; the
+        // context has no transform, so no CSS-px to device-px scaling is applied
+        // to drawing coordinates.
+        let rendering_context = PaintRenderingContext2D::new(self);
+        rendering_context.set_bitmap_dimensions(size, device_pixel_ratio, context_settings.alpha);
 
         // Step 9
         let paint_size = PaintSize::new(self, size);
 
-        // TODO: Step 10
+        // Steps 6-7: only expose the custom/standard properties that were registered
+        // via `inputProperties`, discarding anything else layout resolved for us.
+        let properties = filter_registered_properties(properties, &input_properties);
+        let properties = StylePropertyMapReadOnly::new(self, properties);
+
+        // Step 10.
+        let arguments = match self.parse_a_list_of_component_values(&argument_syntax, &arguments) {
+            Ok(arguments) => arguments,
+            Err(()) => {
+                debug!("Paint arguments did not match the registered syntax for {}.", name);
+                return self.send_invalid_image(size, device_pixel_ratio, sender);
+            }
+        };
+        let arguments_slice: Vec<JSVal> = arguments.iter()
+            .map(|argument| ObjectValue(argument.reflector().get_jsobject().get()))
+            .collect();
+        let arguments_array = unsafe { HandleValueArray::from_rooted_slice(&arguments_slice) };
+        rooted!(in(cx) let arguments_obj = unsafe { JS_NewArrayObject(cx, &arguments_array) });
+
         // Steps 11-12
         debug!("Invoking paint function {}.", name);
         let args_slice = [
             ObjectValue(rendering_context.reflector().get_jsobject().get()),
             ObjectValue(paint_size.reflector().get_jsobject().get()),
+            ObjectValue(properties.reflector().get_jsobject().get()),
+            ObjectValue(arguments_obj.get()),
         ];
         let args = unsafe { HandleValueArray::from_rooted_slice(&args_slice) };
         rooted!(in(cx) let mut result = UndefinedValue());
@@ -188,16 +233,16 @@ impl PaintWorkletGlobalScope {
         if unsafe { JS_IsExceptionPending(cx) } {
             debug!("Paint function threw an exception {}.", name);
             unsafe { JS_ClearPendingException(cx); }
-            return self.send_invalid_image(size, sender);
+            return self.send_invalid_image(size, device_pixel_ratio, sender);
         }
 
         rendering_context.send_data(sender);
     }
 
-    fn send_invalid_image(&self, size: Size2D<Au>, sender: IpcSender<CanvasData>) {
+    fn send_invalid_image(&self, size: Size2D<Au>, device_pixel_ratio: f32, sender: IpcSender<CanvasData>) {
         debug!("Sending an invalid image.");
-        let width = size.width.to_px().abs() as u32;
-        let height = size.height.to_px().abs() as u32;
+        let width = (size.width.to_px().abs() as f32 * device_pixel_ratio) as u32;
+        let height = (size.height.to_px().abs() as f32 * device_pixel_ratio) as u32;
         let len = (width as usize) * (height as usize) * 4;
         let pixel = [0xFF, 0x00, 0x00, 0xFF];
         let bytes: Vec<u8> = pixel.iter().cloned().cycle().take(len).collect();
@@ -214,6 +259,111 @@ impl PaintWorkletGlobalScope {
         let canvas_data = CanvasData::Image(image_data);
         let _ = sender.send(canvas_data);
     }
+
+    /// Parse a list of raw argument tokens against the `inputArguments` syntax
+    /// that was registered for this paint definition.
+    /// https://drafts.css-houdini.org/css-paint-api/#invoke-a-paint-callback (Step 10)
+    fn parse_a_list_of_component_values(&self,
+                                        syntax: &[SyntaxDescriptor],
+                                        arguments: &[String])
+                                        -> Result<Vec<Root<CSSStyleValue>>, ()>
+    {
+        if syntax.len() != arguments.len() {
+            return Err(());
+        }
+        syntax.iter()
+            .zip(arguments.iter())
+            .map(|(descriptor, argument)| {
+                if descriptor.matches(argument) {
+                    Ok(CSSStyleValue::new(self, DOMString::from(argument.clone())))
+                } else {
+                    Err(())
+                }
+            })
+            .collect()
+    }
+}
+
+/// The number of global scopes kept per paint worklet.
+/// The spec deliberately requires more than one, so that authors can't rely on
+/// instance or global state persisting between paint invocations.
+/// https://drafts.css-houdini.org/css-paint-api/#paint-worklet-statelessness
+const PAINT_WORKLET_GLOBAL_SCOPE_POOL_SIZE: usize = 2;
+
+/// A pool of `PaintWorkletGlobalScope`s between which paint invocations are
+/// rotated to enforce the spec's statelessness guarantee. Every module added
+/// to the worklet is evaluated in *each* scope in the pool via
+/// `evaluate_a_module`, so a `RegisterPaint` call the author's module makes
+/// takes effect on every scope, not just whichever one happened to run the
+/// module first.
+/// https://drafts.css-houdini.org/css-paint-api/#drawing-an-image
+///
+/// This struct is expected to outlive any single call into it, so it holds
+/// `JS<PaintWorkletGlobalScope>` (traced, persistent references) rather than
+/// `Root<PaintWorkletGlobalScope>` (a stack-only rooting guard scoped to a
+/// single call frame) — whatever owns a `PaintWorkletGlobalScopePool` is
+/// responsible for tracing it.
+#[derive(JSTraceable, HeapSizeOf)]
+pub struct PaintWorkletGlobalScopePool {
+    scopes: Vec<JS<PaintWorkletGlobalScope>>,
+    next: Cell<usize>,
+}
+
+impl PaintWorkletGlobalScopePool {
+    #[allow(unsafe_code)]
+    pub fn new(runtime: &Runtime,
+               pipeline_id: PipelineId,
+               base_url: ServoUrl,
+               init: &WorkletGlobalScopeInit)
+               -> PaintWorkletGlobalScopePool
+    {
+        debug!("Creating a pool of {} paint worklet global scopes for pipeline {}.",
+               PAINT_WORKLET_GLOBAL_SCOPE_POOL_SIZE, pipeline_id);
+        let scopes = (0..PAINT_WORKLET_GLOBAL_SCOPE_POOL_SIZE)
+            .map(|_| JS::from_ref(&*PaintWorkletGlobalScope::new(runtime, pipeline_id, base_url.clone(), init)))
+            .collect();
+        PaintWorkletGlobalScopePool {
+            scopes: scopes,
+            next: Cell::new(0),
+        }
+    }
+
+    /// Evaluate a worklet module against every scope in the pool, so that any
+    /// `RegisterPaint` calls it makes are visible regardless of which scope
+    /// `perform_a_worklet_task` later rotates to for a given paint. The
+    /// `Worklet::AddModule` handler for a paint worklet should route its
+    /// fetched module source through this method instead of evaluating it in
+    /// a single global scope.
+    ///
+    /// Every scope is given a chance to evaluate the module, even if an
+    /// earlier scope's evaluation fails: a registration failure in one scope
+    /// must not leave the others out of sync with each other. If any scope
+    /// failed, the first error encountered is returned once all scopes have
+    /// been evaluated.
+    /// https://drafts.css-houdini.org/worklets/#dom-worklet-addmodule
+    pub fn evaluate_a_module(&self, url: ServoUrl) -> Fallible<()> {
+        let mut result = Ok(());
+        for scope in &self.scopes {
+            let scope_result = scope.worklet_global.evaluate_a_module(url.clone());
+            if result.is_ok() {
+                result = scope_result;
+            }
+        }
+        result
+    }
+
+    /// Pick a scope to perform this task on, round-robin, so that no scope's
+    /// `paint_class_instances` is ever drawn from on two consecutive paints.
+    pub fn perform_a_worklet_task(&self, task: PaintWorkletTask) {
+        let index = self.next.get();
+        self.next.set(next_pool_index(index, self.scopes.len()));
+        self.scopes[index].perform_a_worklet_task(task);
+    }
+}
+
+/// The next index to dispatch to in a pool of `len` scopes, wrapping around.
+fn next_pool_index(current: usize, len: usize) -> usize {
+    (current + 1) % len
 }
 
 impl PaintWorkletGlobalScopeMethods for PaintWorkletGlobalScope {
@@ -252,14 +402,21 @@ impl PaintWorkletGlobalScopeMethods for PaintWorkletGlobalScope {
             .unwrap_or_default();
         debug!("Got {:?}.", input_arguments);
 
-        // TODO: Steps 10-11.
+        // Steps 10-11.
+        debug!("Parsing input argument syntax.");
+        let argument_syntax = input_arguments.iter()
+            .map(|argument| SyntaxDescriptor::parse(argument))
+            .collect::<Result<Vec<_>, ()>>()
+            .map_err(|()| Error::Type(String::from("Invalid inputArguments syntax descriptor.")))?;
+        debug!("Got {:?}.", argument_syntax);
 
         // Steps 12-13.
-        debug!("Getting alpha.");
+        debug!("Getting context alpha.");
         let alpha: bool =
             unsafe { get_property(cx, paint_obj.handle(), "alpha", ()) }?
             .unwrap_or(true);
-        debug!("Got {:?}.", alpha);
+        let context_settings = PaintRenderingContext2DSettings { alpha: alpha };
+        debug!("Got {:?}.", context_settings);
 
         // Step 14
         if unsafe { !IsConstructor(paint_obj.get()) } {
@@ -282,26 +439,79 @@ impl PaintWorkletGlobalScopeMethods for PaintWorkletGlobalScope {
         }
 
         // Step 19.
-        let context = PaintRenderingContext2D::new(self);
+        // A fresh rendering context is created per invocation (see Step 8 of
+        // "invoke a paint callback"), so there is nothing more to do here than
+        // record the resolved settings.
         let definition = PaintDefinition::new(paint_val.handle(),
                                               paint_function.handle(),
                                               input_properties,
-                                              alpha,
-                                              &*context);
+                                              argument_syntax,
+                                              context_settings);
 
         // Step 20.
         debug!("Registering definition {}.", name);
-        self.paint_definitions.borrow_mut().insert(name, definition);
+        self.paint_definitions.borrow_mut().insert(name.clone(), definition);
 
-        // TODO: Step 21.
+        // Step 21.
+        // Construct the paint class instance now, rather than lazily the first
+        // time the image is painted: `invoke_a_paint_callback` runs on the
+        // paint-critical path, and deferring construction there would run
+        // author constructor code on it with unpredictable latency.
+        // https://github.com/servo/servo/issues/17377
+        debug!("Prepopulating paint class instance {}.", name);
+        rooted!(in(cx) let mut instance = null_mut());
+        let args = HandleValueArray::new();
+        unsafe { Construct1(cx, paint_val.handle(), &args, instance.handle_mut()); }
+        if unsafe { JS_IsExceptionPending(cx) } {
+            debug!("Paint constructor threw an exception during registration {}.", name);
+            unsafe { JS_ClearPendingException(cx); }
+            self.paint_definitions.borrow_mut().get_mut(&name)
+                .expect("Vanishing paint definition.")
+                .constructor_valid_flag.set(false);
+        } else {
+            let heap = Box::new(Heap::default());
+            heap.set(ObjectValue(instance.get()));
+            self.paint_class_instances.borrow_mut().insert(name, heap);
+        }
 
         Ok(())
     }
 }
 
+/// Restrict a list of resolved `(property, value)` pairs down to just the ones
+/// that were named in the paint definition's `inputProperties`.
+/// https://drafts.css-houdini.org/css-paint-api/#invoke-a-paint-callback (Steps 6-7)
+fn filter_registered_properties(properties: Vec<(Atom, String)>,
+                                input_properties: &[DOMString])
+                                -> Vec<(Atom, String)>
+{
+    let registered: Vec<Atom> = input_properties.iter().cloned().map(Atom::from).collect();
+    properties.into_iter()
+        .filter(|&(ref name, _)| registered.contains(name))
+        .collect()
+}
+
 /// Tasks which can be peformed by a paint worklet
+///
+/// `DrawAPaintImage`'s `f32` is the device pixel ratio in effect for the
+/// document the image is being painted into; the caller on the layout side
+/// is responsible for resolving it from the document's window size data
+/// before constructing this task.
 pub enum PaintWorkletTask {
-    DrawAPaintImage(Atom, Size2D<Au>, IpcSender<CanvasData>)
+    DrawAPaintImage(Atom, Size2D<Au>, f32, Vec<(Atom, String)>, Vec<String>, IpcSender<CanvasData>)
+}
+
+/// The resolved value of the class's static `alpha` property.
+/// https://drafts.css-houdini.org/css-paint-api/#dictdef-paintrenderingcontext2dsettings
+#[derive(Clone, Debug, JSTraceable, HeapSizeOf, PartialEq)]
+struct PaintRenderingContext2DSettings {
+    alpha: bool,
+}
+
+impl Default for PaintRenderingContext2DSettings {
+    fn default() -> PaintRenderingContext2DSettings {
+        PaintRenderingContext2DSettings { alpha: true }
+    }
 }
 
 /// A paint definition
@@ -315,19 +525,20 @@ struct PaintDefinition {
     paint_function: Heap<JSVal>,
     constructor_valid_flag: Cell<bool>,
     input_properties: Vec<DOMString>,
-    context_alpha_flag: bool,
-    // TODO: the spec calls for fresh rendering contexts each time a paint image is drawn,
-    // but to avoid having the primary worklet thread create a new renering context,
-    // we recycle them.
-    context: JS<PaintRenderingContext2D>,
+    /// The parsed `inputArguments` syntax descriptors, one per registered argument.
+    /// https://drafts.css-houdini.org/css-paint-api/#dom-paintworkletglobalscope-registerpaint
+    argument_syntax: Vec<SyntaxDescriptor>,
+    /// The resolved `PaintRenderingContext2DSettings`, applied to a fresh
+    /// `PaintRenderingContext2D` on every invocation.
+    context_settings: PaintRenderingContext2DSettings,
 }
 
 impl PaintDefinition {
     fn new(class_constructor: HandleValue,
            paint_function: HandleValue,
            input_properties: Vec<DOMString>,
-           alpha: bool,
-           context: &PaintRenderingContext2D)
+           argument_syntax: Vec<SyntaxDescriptor>,
+           context_settings: PaintRenderingContext2DSettings)
            -> Box<PaintDefinition>
     {
         let result = Box::new(PaintDefinition {
@@ -335,11 +546,349 @@ impl PaintDefinition {
             paint_function: Heap::default(),
             constructor_valid_flag: Cell::new(true),
             input_properties: input_properties,
-            context_alpha_flag: alpha,
-            context: JS::from_ref(context),
+            argument_syntax: argument_syntax,
+            context_settings: context_settings,
         });
         result.class_constructor.set(class_constructor.get());
         result.paint_function.set(paint_function.get());
         result
     }
 }
+
+/// A single `<syntax-component>` of a registered `inputArguments` entry,
+/// e.g. the `<length>` in `"<length>+"`.
+/// https://drafts.css-houdini.org/css-properties-values-api/#syntax-component
+#[derive(Clone, Debug, JSTraceable, HeapSizeOf, PartialEq)]
+enum SyntaxComponentType {
+    Length,
+    Number,
+    Percentage,
+    LengthPercentage,
+    Color,
+    Image,
+    Url,
+    Integer,
+    Angle,
+    Time,
+    Resolution,
+    TransformFunction,
+    CustomIdent,
+    String,
+}
+
+/// A multiplier applied to a `<syntax-component>`: `+` for a whitespace-separated
+/// list, `#` for a comma-separated list.
+/// https://drafts.css-houdini.org/css-properties-values-api/#multipliers
+#[derive(Clone, Debug, JSTraceable, HeapSizeOf, PartialEq)]
+enum Multiplier {
+    Space,
+    Comma,
+}
+
+#[derive(Clone, Debug, JSTraceable, HeapSizeOf, PartialEq)]
+struct SyntaxComponent {
+    type_: SyntaxComponentType,
+    multiplier: Option<Multiplier>,
+}
+
+impl SyntaxComponent {
+    /// Parse a single `<type>`, `<type>+` or `<type>#` component.
+    fn parse(input: &str) -> Result<SyntaxComponent, ()> {
+        let (body, multiplier) = if input.ends_with('+') {
+            (&input[..input.len() - 1], Some(Multiplier::Space))
+        } else if input.ends_with('#') {
+            (&input[..input.len() - 1], Some(Multiplier::Comma))
+        } else {
+            (input, None)
+        };
+        if !body.starts_with('<') || !body.ends_with('>') || body.len() < 3 {
+            return Err(());
+        }
+        let type_ = match &body[1..body.len() - 1] {
+            "length" => SyntaxComponentType::Length,
+            "number" => SyntaxComponentType::Number,
+            "percentage" => SyntaxComponentType::Percentage,
+            "length-percentage" => SyntaxComponentType::LengthPercentage,
+            "color" => SyntaxComponentType::Color,
+            "image" => SyntaxComponentType::Image,
+            "url" => SyntaxComponentType::Url,
+            "integer" => SyntaxComponentType::Integer,
+            "angle" => SyntaxComponentType::Angle,
+            "time" => SyntaxComponentType::Time,
+            "resolution" => SyntaxComponentType::Resolution,
+            "transform-function" => SyntaxComponentType::TransformFunction,
+            "custom-ident" => SyntaxComponentType::CustomIdent,
+            "string" => SyntaxComponentType::String,
+            _ => return Err(()),
+        };
+        Ok(SyntaxComponent { type_: type_, multiplier: multiplier })
+    }
+
+    /// Whether a raw argument token (or, when this component carries a `+`/`#`
+    /// multiplier, a whitespace- or comma-separated list of tokens) matches this
+    /// component's type.
+    /// https://drafts.css-houdini.org/css-properties-values-api/#multipliers
+    fn matches(&self, input: &str) -> bool {
+        let input = input.trim();
+        match self.multiplier {
+            None => self.matches_single(input),
+            Some(Multiplier::Space) => {
+                let items: Vec<&str> = input.split_whitespace().collect();
+                !items.is_empty() && items.iter().all(|item| self.matches_single(item))
+            }
+            Some(Multiplier::Comma) => {
+                let items: Vec<&str> = input.split(',').map(|item| item.trim()).collect();
+                !items.is_empty() && items.iter().all(|item| self.matches_single(item))
+            }
+        }
+    }
+
+    /// Whether a single raw token (without any multiplier applied) matches this
+    /// component's type.
+    /// TODO: this only does a lightweight lexical check; it does not build a
+    /// full CSS value parser (see `cssparser`) for each syntax component type.
+    fn matches_single(&self, input: &str) -> bool {
+        if input.is_empty() {
+            return false;
+        }
+        match self.type_ {
+            SyntaxComponentType::Length => is_length(input),
+            SyntaxComponentType::Number => input.parse::<f64>().is_ok(),
+            SyntaxComponentType::Integer => input.parse::<i64>().is_ok(),
+            SyntaxComponentType::Percentage => is_percentage(input),
+            SyntaxComponentType::LengthPercentage => is_length(input) || is_percentage(input),
+            SyntaxComponentType::Color => is_color(input),
+            SyntaxComponentType::Image | SyntaxComponentType::Url =>
+                input.starts_with("url(") && input.ends_with(')'),
+            SyntaxComponentType::Angle => is_dimension(input, &["deg", "grad", "rad", "turn"]),
+            SyntaxComponentType::Time => is_dimension(input, &["s", "ms"]),
+            SyntaxComponentType::Resolution => is_dimension(input, &["dpi", "dpcm", "dppx"]),
+            SyntaxComponentType::TransformFunction =>
+                input.ends_with(')') &&
+                input.find('(').map_or(false, |paren| is_custom_ident(&input[..paren])),
+            SyntaxComponentType::CustomIdent => is_custom_ident(input),
+            SyntaxComponentType::String =>
+                input.len() >= 2 &&
+                ((input.starts_with('"') && input.ends_with('"')) ||
+                 (input.starts_with('\'') && input.ends_with('\''))),
+        }
+    }
+}
+
+/// Split a `<number><unit>` token into its numeric prefix and unit suffix,
+/// e.g. `"10px"` into `("10", "px")`.
+fn split_number_and_unit(input: &str) -> (&str, &str) {
+    let split_at = input.find(|c: char| c.is_alphabetic() || c == '%').unwrap_or(input.len());
+    input.split_at(split_at)
+}
+
+/// Whether `input` is a `<number>` followed by one of `units` (case-insensitively).
+fn is_dimension(input: &str, units: &[&str]) -> bool {
+    let (number, unit) = split_number_and_unit(input);
+    number.parse::<f64>().is_ok() && units.iter().any(|u| unit.eq_ignore_ascii_case(u))
+}
+
+/// https://drafts.csswg.org/css-values/#lengths
+const LENGTH_UNITS: &'static [&'static str] = &[
+    "em", "ex", "ch", "rem", "vw", "vh", "vmin", "vmax",
+    "cm", "mm", "q", "in", "pt", "pc", "px",
+];
+
+fn is_length(input: &str) -> bool {
+    input == "0" || is_dimension(input, LENGTH_UNITS)
+}
+
+fn is_percentage(input: &str) -> bool {
+    let (number, unit) = split_number_and_unit(input);
+    unit == "%" && number.parse::<f64>().is_ok()
+}
+
+fn is_custom_ident(input: &str) -> bool {
+    !input.is_empty() &&
+    input.chars().next().map_or(false, |c| c.is_alphabetic() || c == '_' || c == '-') &&
+    input.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+}
+
+const CSS_COLOR_FUNCTIONS: &'static [&'static str] = &["rgb(", "rgba(", "hsl(", "hsla("];
+
+/// The CSS Color Level 3 keyword set, lower-cased.
+/// https://drafts.csswg.org/css-color-3/#svg-color
+const CSS_NAMED_COLORS: &'static [&'static str] = &[
+    "transparent", "currentcolor",
+    "aliceblue", "antiquewhite", "aqua", "aquamarine", "azure", "beige", "bisque",
+    "black", "blanchedalmond", "blue", "blueviolet", "brown", "burlywood",
+    "cadetblue", "chartreuse", "chocolate", "coral", "cornflowerblue", "cornsilk",
+    "crimson", "cyan", "darkblue", "darkcyan", "darkgoldenrod", "darkgray",
+    "darkgreen", "darkgrey", "darkkhaki", "darkmagenta", "darkolivegreen",
+    "darkorange", "darkorchid", "darkred", "darksalmon", "darkseagreen",
+    "darkslateblue", "darkslategray", "darkslategrey", "darkturquoise",
+    "darkviolet", "deeppink", "deepskyblue", "dimgray", "dimgrey", "dodgerblue",
+    "firebrick", "floralwhite", "forestgreen", "fuchsia", "gainsboro",
+    "ghostwhite", "gold", "goldenrod", "gray", "green", "greenyellow", "grey",
+    "honeydew", "hotpink", "indianred", "indigo", "ivory", "khaki", "lavender",
+    "lavenderblush", "lawngreen", "lemonchiffon", "lightblue", "lightcoral",
+    "lightcyan", "lightgoldenrodyellow", "lightgray", "lightgreen", "lightgrey",
+    "lightpink", "lightsalmon", "lightseagreen", "lightskyblue",
+    "lightslategray", "lightslategrey", "lightsteelblue", "lightyellow",
+    "lime", "limegreen", "linen", "magenta", "maroon", "mediumaquamarine",
+    "mediumblue", "mediumorchid", "mediumpurple", "mediumseagreen",
+    "mediumslateblue", "mediumspringgreen", "mediumturquoise",
+    "mediumvioletred", "midnightblue", "mintcream", "mistyrose", "moccasin",
+    "navajowhite", "navy", "oldlace", "olive", "olivedrab", "orange",
+    "orangered", "orchid", "palegoldenrod", "palegreen", "paleturquoise",
+    "palevioletred", "papayawhip", "peachpuff", "peru", "pink", "plum",
+    "powderblue", "purple", "red", "rosybrown", "royalblue", "saddlebrown",
+    "salmon", "sandybrown", "seagreen", "seashell", "sienna", "silver",
+    "skyblue", "slateblue", "slategray", "slategrey", "snow", "springgreen",
+    "steelblue", "tan", "teal", "thistle", "tomato", "turquoise", "violet",
+    "wheat", "white", "whitesmoke", "yellow", "yellowgreen",
+];
+
+fn is_hex_color(input: &str) -> bool {
+    let len = input.len();
+    input.starts_with('#') &&
+    (len == 4 || len == 5 || len == 7 || len == 9) &&
+    input[1..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn is_color(input: &str) -> bool {
+    let lower = input.to_ascii_lowercase();
+    is_hex_color(&lower) ||
+    CSS_COLOR_FUNCTIONS.iter().any(|prefix| lower.starts_with(prefix) && lower.ends_with(')')) ||
+    CSS_NAMED_COLORS.contains(&lower.as_str())
+}
+
+/// A parsed `inputArguments` entry: either the universal `"*"` syntax,
+/// or one or more alternative `<syntax-component>`s separated by `|`.
+/// https://drafts.css-houdini.org/css-properties-values-api/#parsing-syntax
+#[derive(Clone, Debug, JSTraceable, HeapSizeOf, PartialEq)]
+enum SyntaxDescriptor {
+    Universal,
+    Components(Vec<SyntaxComponent>),
+}
+
+impl SyntaxDescriptor {
+    /// Parse a single `inputArguments` entry, e.g. `"<length>"`, `"<color>"`,
+    /// `"<number>+"`, or `"*"`.
+    fn parse(input: &DOMString) -> Result<SyntaxDescriptor, ()> {
+        let input = input.trim();
+        if input == "*" {
+            return Ok(SyntaxDescriptor::Universal);
+        }
+        let components = input.split('|')
+            .map(|component| SyntaxComponent::parse(component.trim()))
+            .collect::<Result<Vec<_>, ()>>()?;
+        if components.is_empty() {
+            return Err(());
+        }
+        Ok(SyntaxDescriptor::Components(components))
+    }
+
+    /// Whether a single raw argument token matches this descriptor.
+    fn matches(&self, input: &str) -> bool {
+        match *self {
+            SyntaxDescriptor::Universal => true,
+            SyntaxDescriptor::Components(ref components) =>
+                components.iter().any(|component| component.matches(input)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SyntaxComponent;
+    use super::SyntaxDescriptor;
+    use super::filter_registered_properties;
+    use super::next_pool_index;
+    use dom::bindings::str::DOMString;
+    use servo_atoms::Atom;
+
+    #[test]
+    fn next_pool_index_wraps_around() {
+        assert_eq!(next_pool_index(0, 2), 1);
+        assert_eq!(next_pool_index(1, 2), 0);
+        assert_eq!(next_pool_index(2, 3), 0);
+    }
+
+    #[test]
+    fn filter_registered_properties_keeps_only_registered_names() {
+        let input_properties = vec![DOMString::from("--foo"), DOMString::from("color")];
+        let properties = vec![
+            (Atom::from("--foo"), String::from("1")),
+            (Atom::from("color"), String::from("red")),
+            (Atom::from("--unregistered"), String::from("2")),
+        ];
+        let filtered = filter_registered_properties(properties, &input_properties);
+        assert_eq!(filtered, vec![
+            (Atom::from("--foo"), String::from("1")),
+            (Atom::from("color"), String::from("red")),
+        ]);
+    }
+
+    #[test]
+    fn filter_registered_properties_empty_input_properties_yields_nothing() {
+        let properties = vec![(Atom::from("color"), String::from("red"))];
+        let filtered = filter_registered_properties(properties, &[]);
+        assert!(filtered.is_empty());
+    }
+
+    #[test]
+    fn syntax_component_parse_rejects_unknown_type() {
+        assert!(SyntaxComponent::parse("<bogus>").is_err());
+    }
+
+    #[test]
+    fn syntax_component_length_matches_numbers_with_units_only() {
+        let length = SyntaxComponent::parse("<length>").unwrap();
+        assert!(length.matches("0"));
+        assert!(length.matches("10px"));
+        assert!(length.matches("1.5em"));
+        assert!(!length.matches("item"));
+        assert!(!length.matches("system"));
+        assert!(!length.matches("px"));
+    }
+
+    #[test]
+    fn syntax_component_color_rejects_arbitrary_words() {
+        let color = SyntaxComponent::parse("<color>").unwrap();
+        assert!(color.matches("red"));
+        assert!(color.matches("#ff0000"));
+        assert!(color.matches("rgba(0, 0, 0, 0.5)"));
+        assert!(!color.matches("banana"));
+    }
+
+    #[test]
+    fn syntax_component_space_multiplier_requires_every_token_to_match() {
+        let lengths = SyntaxComponent::parse("<length>+").unwrap();
+        assert!(lengths.matches("10px 20px 0"));
+        assert!(!lengths.matches("10px banana"));
+        assert!(!lengths.matches(""));
+    }
+
+    #[test]
+    fn syntax_component_comma_multiplier_requires_every_token_to_match() {
+        let numbers = SyntaxComponent::parse("<number>#").unwrap();
+        assert!(numbers.matches("1, 2, 3"));
+        assert!(!numbers.matches("1, banana"));
+    }
+
+    #[test]
+    fn syntax_component_without_multiplier_rejects_lists() {
+        let length = SyntaxComponent::parse("<length>").unwrap();
+        assert!(!length.matches("10px 20px"));
+    }
+
+    #[test]
+    fn syntax_descriptor_universal_matches_anything() {
+        let universal = SyntaxDescriptor::parse(&DOMString::from("*")).unwrap();
+        assert!(universal.matches("anything at all"));
+    }
+
+    #[test]
+    fn syntax_descriptor_alternatives_match_if_any_component_matches() {
+        let descriptor = SyntaxDescriptor::parse(&DOMString::from("<length> | <color>")).unwrap();
+        assert!(descriptor.matches("10px"));
+        assert!(descriptor.matches("red"));
+        assert!(!descriptor.matches("banana"));
+    }
+}
@@ -0,0 +1,62 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use dom::bindings::codegen::Bindings::StylePropertyMapReadOnlyBinding;
+use dom::bindings::codegen::Bindings::StylePropertyMapReadOnlyBinding::StylePropertyMapReadOnlyMethods;
+use dom::bindings::js::Root;
+use dom::bindings::reflector::{Reflector, reflect_dom_object};
+use dom::bindings::str::DOMString;
+use dom::paintworkletglobalscope::PaintWorkletGlobalScope;
+use dom_struct::dom_struct;
+use servo_atoms::Atom;
+
+/// https://drafts.css-houdini.org/css-paint-api/#stylepropertymapreadonly
+#[dom_struct]
+pub struct StylePropertyMapReadOnly {
+    reflector: Reflector,
+    properties: Vec<(Atom, DOMString)>,
+}
+
+impl StylePropertyMapReadOnly {
+    fn new_inherited(properties: Vec<(Atom, DOMString)>) -> StylePropertyMapReadOnly {
+        StylePropertyMapReadOnly {
+            reflector: Reflector::new(),
+            properties: properties,
+        }
+    }
+
+    /// Create a new read-only style property map from a list of
+    /// (property name, serialized value) pairs, as collected for the
+    /// `properties` argument of a paint callback.
+    /// https://drafts.css-houdini.org/css-paint-api/#paint-2
+    pub fn new(global: &PaintWorkletGlobalScope, properties: Vec<(Atom, String)>)
+               -> Root<StylePropertyMapReadOnly>
+    {
+        let properties = properties.into_iter()
+            .map(|(name, value)| (name, DOMString::from(value)))
+            .collect();
+        reflect_dom_object(box StylePropertyMapReadOnly::new_inherited(properties),
+                            global,
+                            StylePropertyMapReadOnlyBinding::Wrap)
+    }
+}
+
+impl StylePropertyMapReadOnlyMethods for StylePropertyMapReadOnly {
+    /// https://drafts.css-houdini.org/css-paint-api/#dom-stylepropertymapreadonly-get
+    fn Get(&self, property: DOMString) -> Option<DOMString> {
+        let property = Atom::from(property);
+        self.properties.iter()
+            .find(|&&(ref name, _)| *name == property)
+            .map(|&(_, ref value)| value.clone())
+    }
+
+    /// https://drafts.css-houdini.org/css-paint-api/#dom-stylepropertymapreadonly-getall
+    fn GetAll(&self, property: DOMString) -> Vec<DOMString> {
+        let property = Atom::from(property);
+        self.properties.iter()
+            .filter(|&&(ref name, _)| *name == property)
+            .map(|&(_, ref value)| value.clone())
+            .collect()
+    }
+}